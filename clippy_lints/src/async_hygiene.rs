@@ -0,0 +1,385 @@
+//! Lints that keep async bodies well-behaved: not calling blocking functions
+//! and not holding a non-async-aware lock guard across an `.await`.
+//!
+//! Both checks share a single `check_body` callback because both need the
+//! same `typeck_results` for the body (the blocking-call walk needs it for
+//! call resolution, the guard check needs `generator_interior_types`), and
+//! typeck'ing the body is not free.
+
+use crate::utils::{match_def_path, paths, span_lint_and_help, span_lint_and_note};
+use rustc_hir::def_id::{DefId, CRATE_DEF_INDEX, LOCAL_CRATE};
+use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
+use rustc_hir::{AsyncGeneratorKind, Body, BodyId, Expr, ExprKind, GeneratorKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::hir::map::Map;
+use rustc_middle::ty::{GeneratorInteriorTypeCause, TypeckResults};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::Span;
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for calls to blocking functions from within
+    /// an async body.
+    ///
+    /// **Why is this bad?** Blocking the thread a runtime is driving futures
+    /// on stalls every other task scheduled onto that thread until the call
+    /// returns.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    ///
+    /// ```rust,ignore
+    /// async fn foo() {
+    ///   std::thread::sleep(std::time::Duration::from_secs(1));
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// async fn foo() {
+    ///   tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    /// }
+    /// ```
+    pub MAY_BLOCK,
+    correctness,
+    "Using blocking functions in async code can slow down the async runtime"
+}
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for calls to await while holding a
+    /// non-async-aware lock guard, i.e. a `MutexGuard`, `RwLockReadGuard` or
+    /// `RwLockWriteGuard` from `std::sync`, or their `parking_lot`
+    /// equivalents.
+    ///
+    /// **Why is this bad?** The Mutex and RwLock types found in std::sync and
+    /// parking_lot are not designed to operate in an async context across
+    /// await points.
+    ///
+    /// There are two potential solutions. One is to use an asynx-aware Mutex
+    /// type. Many asynchronous foundation crates provide such a Mutex type. The
+    /// other solution is to ensure the mutex is unlocked before calling await,
+    /// either by introducing a scope or an explicit call to Drop::drop.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    ///
+    /// ```rust,ignore
+    /// use std::sync::Mutex;
+    ///
+    /// async fn foo(x: &Mutex<u32>) {
+    ///   let guard = x.lock().unwrap();
+    ///   *guard += 1;
+    ///   bar.await;
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// use std::sync::Mutex;
+    ///
+    /// async fn foo(x: &Mutex<u32>) {
+    ///   {
+    ///     let guard = x.lock().unwrap();
+    ///     *guard += 1;
+    ///   }
+    ///   bar.await;
+    /// }
+    /// ```
+    pub AWAIT_HOLDING_LOCK,
+    correctness,
+    "Holding a non-async-aware lock guard across an await point"
+}
+
+/// Fully-qualified paths to blocking functions configured via the
+/// `blocking-functions` clippy.toml option, e.g. `db::query_sync`.
+pub struct AsyncHygiene {
+    conf_blocking_functions: Vec<String>,
+    configured_blocking_functions: Vec<DefId>,
+    runtime: Runtime,
+}
+
+impl AsyncHygiene {
+    pub fn new(conf_blocking_functions: Vec<String>) -> Self {
+        Self {
+            conf_blocking_functions,
+            configured_blocking_functions: Vec::new(),
+            runtime: Runtime::Unknown,
+        }
+    }
+}
+
+impl_lint_pass!(AsyncHygiene => [MAY_BLOCK, AWAIT_HOLDING_LOCK]);
+
+impl LateLintPass<'_> for AsyncHygiene {
+    fn check_crate(&mut self, cx: &LateContext<'_>) {
+        self.configured_blocking_functions = self
+            .conf_blocking_functions
+            .iter()
+            .filter_map(|path| resolve_def_path(cx, path))
+            .collect();
+        self.runtime = detect_runtime(cx);
+    }
+
+    fn check_body(&mut self, cx: &LateContext<'_>, body: &'_ Body<'_>) {
+        use AsyncGeneratorKind::{Block, Closure, Fn};
+        if let Some(GeneratorKind::Async(Block | Closure | Fn)) = body.generator_kind {
+            let body_id = BodyId {
+                hir_id: body.value.hir_id,
+            };
+            let def_id = cx.tcx.hir().body_owner_def_id(body_id);
+            let typeck_results = cx.tcx.typeck(def_id);
+            check_held_locks(cx, &typeck_results.generator_interior_types, body.value.span);
+
+            let mut visitor = BlockingCallVisitor {
+                cx,
+                typeck_results,
+                pass: self,
+            };
+            visitor.visit_expr(body.value);
+        }
+    }
+}
+
+/// Reports interior (held-across-await) types that are non-async-aware lock
+/// guards. Types that merely happen to be held live across an await point
+/// but aren't guards (e.g. a `Vec<u8>`) aren't blocking and are ignored here;
+/// blocking *calls* are handled separately by `BlockingCallVisitor`.
+fn check_held_locks(cx: &LateContext<'_>, ty_causes: &[GeneratorInteriorTypeCause<'_>], span: Span) {
+    for ty_cause in ty_causes {
+        if let rustc_middle::ty::Adt(adt, _) = ty_cause.ty.kind() {
+            if is_guard(cx, adt.did) {
+                span_lint_and_note(
+                    cx,
+                    AWAIT_HOLDING_LOCK,
+                    ty_cause.span,
+                    &format!("this {} is held across an await point", cx.tcx.item_name(adt.did)),
+                    ty_cause.scope_span.or(Some(span)),
+                    "these are all the await points this lock is held through",
+                );
+            }
+        }
+    }
+}
+
+static GUARD_PATHS: &[paths::Path] = &[
+    &paths::MUTEX_GUARD,
+    &paths::RWLOCK_READ_GUARD,
+    &paths::RWLOCK_WRITE_GUARD,
+    &paths::PARKING_LOT_MUTEX_GUARD,
+    &paths::PARKING_LOT_RWLOCK_READ_GUARD,
+    &paths::PARKING_LOT_RWLOCK_WRITE_GUARD,
+];
+
+fn is_guard(cx: &LateContext<'_>, def_id: DefId) -> bool {
+    GUARD_PATHS.iter().any(|path| match_def_path(cx, def_id, path))
+}
+
+/// Which async runtime (if any) is reachable from the dependency graph.
+/// Determines which async replacement MAY_BLOCK points users at.
+#[derive(Clone, Copy)]
+enum Runtime {
+    Tokio,
+    AsyncStd,
+    /// Neither or both are present: suggest both and let the user pick.
+    Unknown,
+}
+
+fn detect_runtime(cx: &LateContext<'_>) -> Runtime {
+    let mut tokio = false;
+    let mut async_std = false;
+    for &krate in cx.tcx.crates(()) {
+        match cx.tcx.crate_name(krate).as_str() {
+            "tokio" => tokio = true,
+            "async_std" => async_std = true,
+            _ => {},
+        }
+    }
+    match (tokio, async_std) {
+        (true, false) => Runtime::Tokio,
+        (false, true) => Runtime::AsyncStd,
+        _ => Runtime::Unknown,
+    }
+}
+
+/// A blocking std path and the async replacement offered by each runtime we
+/// know how to suggest for.
+struct AsyncAlternative {
+    blocking: paths::Path,
+    tokio: &'static str,
+    async_std: &'static str,
+}
+
+static ASYNC_ALTERNATIVES: &[AsyncAlternative] = &[
+    AsyncAlternative {
+        blocking: &paths::THREAD_SLEEP,
+        tokio: "tokio::time::sleep",
+        async_std: "async_std::task::sleep",
+    },
+    AsyncAlternative {
+        blocking: &paths::FS_READ,
+        tokio: "tokio::fs::read",
+        async_std: "async_std::fs::read",
+    },
+    AsyncAlternative {
+        blocking: &paths::FS_WRITE,
+        tokio: "tokio::fs::write",
+        async_std: "async_std::fs::write",
+    },
+    AsyncAlternative {
+        blocking: &paths::FS_READ_TO_STRING,
+        tokio: "tokio::fs::read_to_string",
+        async_std: "async_std::fs::read_to_string",
+    },
+    AsyncAlternative {
+        blocking: &paths::MPSC_RECEIVER_RECV,
+        tokio: "tokio::sync::mpsc::Receiver::recv",
+        async_std: "async_std::channel::Receiver::recv",
+    },
+];
+
+/// Builds the `help:` text attached to a `MAY_BLOCK` lint, naming the
+/// idiomatic async replacement for `def_id` when one is known. Falls back to
+/// generic advice for blocking calls we don't have a catalogued alternative
+/// for (e.g. a user-configured `blocking-functions` entry).
+fn blocking_alternative_help(cx: &LateContext<'_>, def_id: DefId, runtime: Runtime) -> String {
+    let alt = match ASYNC_ALTERNATIVES.iter().find(|alt| match_def_path(cx, def_id, alt.blocking)) {
+        Some(alt) => alt,
+        None => return "consider using a non-blocking alternative from the async ecosystem of your runtime".to_string(),
+    };
+    match runtime {
+        Runtime::Tokio => format!("use `{}` instead", alt.tokio),
+        Runtime::AsyncStd => format!("use `{}` instead", alt.async_std),
+        Runtime::Unknown => format!("use `{}` (tokio) or `{}` (async-std) instead", alt.tokio, alt.async_std),
+    }
+}
+
+/// Resolves a `::`-separated path from `clippy.toml` (e.g. `db::query_sync`
+/// or `tokio::time::sleep`) to the `DefId` it names, by walking item
+/// children from a crate root. Unresolvable paths (typos, paths behind a
+/// `cfg` that isn't active, ...) are silently skipped, mirroring how other
+/// configurable allow/deny-lists in this crate treat unresolvable entries.
+///
+/// The leading segment is tried as an external crate name first (for paths
+/// like `tokio::time::sleep`). If no such crate is loaded, the whole path is
+/// assumed to be rooted in the crate being linted (e.g. a local
+/// `helpers::db::query_sync`), since `cx.tcx.crates(())` never includes
+/// `LOCAL_CRATE`.
+fn resolve_def_path(cx: &LateContext<'_>, path: &str) -> Option<DefId> {
+    let mut segments = path.split("::").peekable();
+    let leading_crate = *segments.peek()?;
+    let mut def_id = match cx
+        .tcx
+        .crates(())
+        .iter()
+        .find(|&&cnum| cx.tcx.crate_name(cnum).as_str() == leading_crate)
+    {
+        Some(&cnum) => {
+            segments.next();
+            DefId {
+                krate: cnum,
+                index: CRATE_DEF_INDEX,
+            }
+        },
+        None => DefId {
+            krate: LOCAL_CRATE,
+            index: CRATE_DEF_INDEX,
+        },
+    };
+    for segment in segments {
+        def_id = cx
+            .tcx
+            .item_children(def_id)
+            .iter()
+            .find(|item| item.ident.name.as_str() == segment)?
+            .res
+            .opt_def_id()?;
+    }
+    Some(def_id)
+}
+
+/// Walks the expressions of a single async body, looking for calls to
+/// blocking functions. Does not descend into nested closures or items: those
+/// get their own `check_body` callback (with their own `typeck_results`), so
+/// following them here would either double-report an async closure or flag a
+/// blocking call that was deliberately moved into a synchronous one (e.g.
+/// handed to `spawn_blocking`).
+struct BlockingCallVisitor<'a, 'tcx> {
+    cx: &'a LateContext<'tcx>,
+    typeck_results: &'tcx TypeckResults<'tcx>,
+    pass: &'a AsyncHygiene,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for BlockingCallVisitor<'a, 'tcx> {
+    type Map = Map<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+        if let ExprKind::Closure(..) = expr.kind {
+            return;
+        }
+
+        let callee = match expr.kind {
+            ExprKind::Call(func, _) => match func.kind {
+                ExprKind::Path(ref qpath) => self.typeck_results.qpath_res(qpath, func.hir_id).opt_def_id(),
+                _ => None,
+            },
+            ExprKind::MethodCall(..) => self.typeck_results.type_dependent_def_id(expr.hir_id),
+            _ => None,
+        };
+
+        if let Some(def_id) = callee {
+            if self.pass.is_blocking(self.cx, def_id) {
+                let help = blocking_alternative_help(self.cx, def_id, self.pass.runtime);
+                span_lint_and_help(
+                    self.cx,
+                    MAY_BLOCK,
+                    expr.span,
+                    "this call can block and will slow down the async runtime",
+                    None,
+                    &help,
+                );
+            }
+        }
+
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Std functions and methods that are known to block the calling thread.
+/// Kept as a flat list of paths so new offenders are a one-line addition.
+///
+/// `IO_READ_READ`/`IO_WRITE_WRITE` are trait methods (`Read::read`,
+/// `Write::write`) rather than inherent ones: a call like `file.read(buf)`
+/// has no inherent `File::read`, so method resolution dispatches through the
+/// `Read` impl and `type_dependent_def_id` in `BlockingCallVisitor` resolves
+/// to the trait method's own `DefId` (`std::io::Read::read`), which is
+/// exactly what these paths name. A plain `match_def_path` is therefore
+/// sufficient here, the same as for the inherent-method entries below;
+/// `tests/ui/may_block.rs` exercises both on a concrete `std::fs::File`.
+static BLOCKING_PATHS: &[paths::Path] = &[
+    &paths::THREAD_SLEEP,
+    &paths::FS_FILE_OPEN,
+    &paths::FS_READ,
+    &paths::FS_WRITE,
+    &paths::FS_READ_TO_STRING,
+    &paths::IO_READ_READ,
+    &paths::IO_WRITE_WRITE,
+    &paths::TCP_STREAM_CONNECT,
+    &paths::TCP_LISTENER_ACCEPT,
+    &paths::MPSC_RECEIVER_RECV,
+    &paths::MPSC_RECEIVER_RECV_TIMEOUT,
+    &paths::PROCESS_COMMAND_OUTPUT,
+    &paths::PROCESS_COMMAND_STATUS,
+    &paths::PROCESS_COMMAND_SPAWN,
+];
+
+impl AsyncHygiene {
+    fn is_blocking(&self, cx: &LateContext<'_>, def_id: DefId) -> bool {
+        BLOCKING_PATHS.iter().any(|path| match_def_path(cx, def_id, path))
+            || self.configured_blocking_functions.contains(&def_id)
+    }
+}
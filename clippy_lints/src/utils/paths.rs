@@ -0,0 +1,31 @@
+//! Path constants for functions and types that are checked by various lints.
+
+pub type Path = &'static [&'static str];
+
+pub const THREAD_SLEEP: [&str; 3] = ["std", "thread", "sleep"];
+
+pub const FS_FILE_OPEN: [&str; 4] = ["std", "fs", "File", "open"];
+pub const FS_READ: [&str; 3] = ["std", "fs", "read"];
+pub const FS_WRITE: [&str; 3] = ["std", "fs", "write"];
+pub const FS_READ_TO_STRING: [&str; 3] = ["std", "fs", "read_to_string"];
+
+pub const IO_READ_READ: [&str; 4] = ["std", "io", "Read", "read"];
+pub const IO_WRITE_WRITE: [&str; 4] = ["std", "io", "Write", "write"];
+
+pub const TCP_STREAM_CONNECT: [&str; 5] = ["std", "net", "tcp", "TcpStream", "connect"];
+pub const TCP_LISTENER_ACCEPT: [&str; 5] = ["std", "net", "tcp", "TcpListener", "accept"];
+
+pub const MPSC_RECEIVER_RECV: [&str; 5] = ["std", "sync", "mpsc", "Receiver", "recv"];
+pub const MPSC_RECEIVER_RECV_TIMEOUT: [&str; 5] = ["std", "sync", "mpsc", "Receiver", "recv_timeout"];
+
+pub const PROCESS_COMMAND_OUTPUT: [&str; 4] = ["std", "process", "Command", "output"];
+pub const PROCESS_COMMAND_STATUS: [&str; 4] = ["std", "process", "Command", "status"];
+pub const PROCESS_COMMAND_SPAWN: [&str; 4] = ["std", "process", "Command", "spawn"];
+
+pub const MUTEX_GUARD: [&str; 4] = ["std", "sync", "mutex", "MutexGuard"];
+pub const RWLOCK_READ_GUARD: [&str; 4] = ["std", "sync", "rwlock", "RwLockReadGuard"];
+pub const RWLOCK_WRITE_GUARD: [&str; 4] = ["std", "sync", "rwlock", "RwLockWriteGuard"];
+// `parking_lot`'s guards are type aliases of the generic `lock_api` guards.
+pub const PARKING_LOT_MUTEX_GUARD: [&str; 3] = ["lock_api", "mutex", "MutexGuard"];
+pub const PARKING_LOT_RWLOCK_READ_GUARD: [&str; 3] = ["lock_api", "rwlock", "RwLockReadGuard"];
+pub const PARKING_LOT_RWLOCK_WRITE_GUARD: [&str; 3] = ["lock_api", "rwlock", "RwLockWriteGuard"];
@@ -0,0 +1,17 @@
+// edition:2018
+
+#![warn(clippy::may_block)]
+
+mod helpers {
+    pub mod db {
+        pub fn query_sync() -> i32 {
+            42
+        }
+    }
+}
+
+async fn handler() {
+    let _ = helpers::db::query_sync();
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+// edition:2018
+
+#![warn(clippy::await_holding_lock)]
+
+use std::sync::Mutex;
+
+async fn bar() {}
+
+#[rustfmt::skip]
+async fn foo(x: &Mutex<u32>) {
+    let mut guard = x.lock().unwrap();
+    *guard += 1;
+    bar().await;
+}
+
+fn main() {}
@@ -1,19 +1,30 @@
-// run-rustfix
 // edition:2018
 
-#![feature(async_closure)]
-#![warn(clippy::async_yields_async)]
+#![warn(clippy::may_block)]
 
-use core::future::Future;
-use core::pin::Pin;
-use core::task::{Context, Poll};
+use std::io::{Read, Write};
 use std::thread::sleep;
 use std::time::Duration;
 
-
 #[rustfmt::skip]
 fn main() {
     let _g = async {
-        sleep(std::time::Duration::from_secs(1));
+        sleep(Duration::from_secs(1));
+        let _ = std::fs::read("Cargo.toml");
+        let _ = std::fs::write("Cargo.toml", b"data");
+        let _ = std::fs::read_to_string("Cargo.toml");
+        let mut f = std::fs::File::open("Cargo.toml").unwrap();
+        let mut buf = [0u8; 8];
+        let _ = f.read(&mut buf);
+        let _ = f.write(&buf);
+        let _ = std::net::TcpStream::connect("127.0.0.1:0");
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let _ = listener.accept();
+        let (_tx, rx) = std::sync::mpsc::channel::<()>();
+        let _ = rx.recv();
+        let _ = rx.recv_timeout(Duration::from_secs(1));
+        let _ = std::process::Command::new("ls").output();
+        let _ = std::process::Command::new("ls").status();
+        let _ = std::process::Command::new("ls").spawn();
     };
 }